@@ -0,0 +1,63 @@
+//! This module contains `Configuration` - the node's configuration, loaded
+//! from a JSON file at startup.
+use crate::{
+    crypto::{PrivateKey, PublicKey},
+    peer::PeerId,
+};
+use serde::Deserialize;
+use std::{fs::File, io::BufReader, path::Path};
+
+/// Controls how strictly incoming transactions and blocks are validated.
+/// Unrelated to which [`BlockStoreKind`] `Kura` persists them with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Mode {
+    /// Full signature and business-rule validation.
+    Strict,
+    /// Reduced validation, useful for maintenance tooling and benchmarking.
+    Maintenance,
+}
+
+/// Which [`crate::kura::BlockStore`] implementation `Kura` should use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum BlockStoreKind {
+    /// One SCALE-encoded file per block, under `kura_block_store_path`.
+    File,
+    /// An embedded SQLite database, at `kura_block_store_path`.
+    Sqlite,
+}
+
+impl Default for BlockStoreKind {
+    fn default() -> Self {
+        BlockStoreKind::File
+    }
+}
+
+/// Node-wide configuration, usually deserialized from a JSON file.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Configuration {
+    pub torii_url: String,
+    pub torii_api_url: String,
+    pub kura_block_store_path: String,
+    #[serde(default)]
+    pub kura_block_store_kind: BlockStoreKind,
+    pub mode: Mode,
+    pub trusted_peers: Option<Vec<PeerId>>,
+    pub max_faulty_peers: u32,
+    public_key: PublicKey,
+    private_key: PrivateKey,
+}
+
+impl Configuration {
+    /// Loads configuration from a JSON file at `path`.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Configuration, String> {
+        let file = File::open(path)
+            .map_err(|e| format!("Failed to open configuration file: {}", e))?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Failed to parse configuration file: {}", e))
+    }
+
+    /// This node's key pair.
+    pub fn key_pair(&self) -> (PublicKey, PrivateKey) {
+        (self.public_key.clone(), self.private_key.clone())
+    }
+}
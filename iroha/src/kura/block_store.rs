@@ -0,0 +1,136 @@
+//! The [`BlockStore`] trait and its filesystem-backed implementation.
+use crate::prelude::*;
+use parity_scale_codec::{Decode, Encode};
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// Persists and retrieves committed blocks. Implementations may back this with
+/// a filesystem, an embedded database, or anything else that can durably keep
+/// an append-only chain of blocks.
+pub trait BlockStore {
+    /// Prepares the store for use (e.g. creating directories or tables). Idempotent.
+    fn open(&mut self) -> Result<(), String>;
+    /// Appends `block` as the new last block.
+    fn append_block(&mut self, block: Block) -> Result<(), String>;
+    /// Reads the block at `height` (1-indexed), if present.
+    fn read_block_by_height(&self, height: u64) -> Option<Block>;
+    /// Reads the block whose header hashes to `hash`, if present.
+    fn read_block_by_hash(&self, hash: Hash) -> Option<Block>;
+    /// Number of blocks currently stored.
+    fn len(&self) -> usize;
+    /// The most recently appended block, if any.
+    fn last(&self) -> Option<Block> {
+        self.read_block_by_height(self.len() as u64)
+    }
+}
+
+/// Stores each block as a SCALE-encoded file named after its height, inside
+/// `block_store_path`.
+pub struct FileBlockStore {
+    block_store_path: PathBuf,
+    height: u64,
+}
+
+impl FileBlockStore {
+    pub fn new(block_store_path: &Path) -> Self {
+        FileBlockStore {
+            block_store_path: block_store_path.to_path_buf(),
+            height: 0,
+        }
+    }
+
+    fn path_for_height(&self, height: u64) -> PathBuf {
+        self.block_store_path.join(height.to_string())
+    }
+}
+
+impl BlockStore for FileBlockStore {
+    fn open(&mut self) -> Result<(), String> {
+        fs::create_dir_all(&self.block_store_path)
+            .map_err(|e| format!("Failed to create block store directory: {}", e))?;
+        self.height = fs::read_dir(&self.block_store_path)
+            .map_err(|e| format!("Failed to read block store directory: {}", e))?
+            .count() as u64;
+        Ok(())
+    }
+
+    fn append_block(&mut self, block: Block) -> Result<(), String> {
+        let height = self.height + 1;
+        let mut file = File::create(self.path_for_height(height))
+            .map_err(|e| format!("Failed to create block file: {}", e))?;
+        file.write_all(&block.encode())
+            .map_err(|e| format!("Failed to write block: {}", e))?;
+        self.height = height;
+        Ok(())
+    }
+
+    fn read_block_by_height(&self, height: u64) -> Option<Block> {
+        let mut bytes = Vec::new();
+        File::open(self.path_for_height(height))
+            .ok()?
+            .read_to_end(&mut bytes)
+            .ok()?;
+        Block::decode(&mut bytes.as_slice()).ok()
+    }
+
+    fn read_block_by_hash(&self, hash: Hash) -> Option<Block> {
+        (1..=self.height).find_map(|height| {
+            self.read_block_by_height(height)
+                .filter(|block| block.hash() == hash)
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.height as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(previous_block_hash: Hash) -> Block {
+        Block::new(Vec::new(), previous_block_hash)
+    }
+
+    fn store() -> FileBlockStore {
+        let path = std::env::temp_dir().join(format!("iroha-test-kura-{}", unique_suffix()));
+        let mut store = FileBlockStore::new(&path);
+        store.open().expect("Failed to open block store.");
+        store
+    }
+
+    fn unique_suffix() -> u128 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards.")
+            .as_nanos()
+    }
+
+    #[test]
+    fn sequential_appends_are_stored_in_order() {
+        let mut store = store();
+        let first = block(Hash::default());
+        let second = block(first.hash());
+
+        store.append_block(first.clone()).expect("Failed to append first block.");
+        store.append_block(second.clone()).expect("Failed to append second block.");
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.last(), Some(second));
+    }
+
+    #[test]
+    fn blocks_round_trip_by_height_and_hash() {
+        let mut store = store();
+        let first = block(Hash::default());
+        store.append_block(first.clone()).expect("Failed to append block.");
+
+        assert_eq!(store.read_block_by_height(1), Some(first.clone()));
+        assert_eq!(store.read_block_by_hash(first.hash()), Some(first));
+        assert_eq!(store.read_block_by_height(2), None);
+    }
+}
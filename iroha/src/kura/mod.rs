@@ -0,0 +1,101 @@
+//! This module contains `Kura` - the block storage engine for Iroha, built on
+//! top of a pluggable [`BlockStore`].
+mod block_store;
+mod sqlite;
+
+pub use block_store::{BlockStore, FileBlockStore};
+pub use sqlite::SqliteBlockStore;
+
+use crate::{config::BlockStoreKind, prelude::*, BlockSender};
+use std::path::Path;
+
+/// Upper bound on the number of heights a single `blocks_in_range` request will
+/// iterate, regardless of what the caller asks for. Protects `BLOCKS_GET` from a
+/// peer requesting an unbounded range.
+const MAX_BLOCKS_IN_RANGE: u64 = 1_000;
+
+/// `Kura` is responsible for persisting committed blocks and serving them back
+/// on request, either to the local `WorldStateView` on startup or to peers
+/// catching up through `Torii`. The actual persistence is delegated to a
+/// [`BlockStore`], chosen according to [`BlockStoreKind`].
+pub struct Kura {
+    block_store: Box<dyn BlockStore + Send + Sync>,
+    blocks_sender: BlockSender,
+}
+
+impl Kura {
+    /// `Kura` constructor, the returned instance still needs to be [`init`](Kura::init)ialized.
+    pub fn new(
+        block_store_kind: BlockStoreKind,
+        block_store_path: &Path,
+        blocks_sender: BlockSender,
+    ) -> Self {
+        let block_store: Box<dyn BlockStore + Send + Sync> = match block_store_kind {
+            BlockStoreKind::Sqlite => Box::new(SqliteBlockStore::new(block_store_path)),
+            BlockStoreKind::File => Box::new(FileBlockStore::new(block_store_path)),
+        };
+        Kura {
+            block_store,
+            blocks_sender,
+        }
+    }
+
+    /// Opens the underlying block store and replays the blocks already
+    /// persisted on disk to the `WorldStateView` via `blocks_sender`.
+    pub async fn init(&mut self) -> Result<(), String> {
+        self.block_store.open()?;
+        for height in 1..=self.block_store.len() as u64 {
+            if let Some(block) = self.block_store.read_block_by_height(height) {
+                self.blocks_sender
+                    .send(block)
+                    .await
+                    .map_err(|e| format!("Failed to replay block: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Persists `block` as the new last block and forwards it to `blocks_sender`,
+    /// the same way `init()` does when replaying blocks already on disk, so that
+    /// a block received from a peer for catch-up also reaches `WorldStateView`.
+    pub async fn store_block(&mut self, block: Block) -> Result<(), String> {
+        self.block_store.append_block(block.clone())?;
+        self.blocks_sender
+            .send(block)
+            .await
+            .map_err(|e| format!("Failed to forward stored block: {}", e))
+    }
+
+    /// Number of blocks currently persisted.
+    pub fn len(&self) -> usize {
+        self.block_store.len()
+    }
+
+    /// The most recently committed block, if any.
+    pub fn last_block(&self) -> Option<Block> {
+        self.block_store.last()
+    }
+
+    /// Looks up a block by its height (1-indexed).
+    pub fn block_by_height(&self, height: u64) -> Option<Block> {
+        self.block_store.read_block_by_height(height)
+    }
+
+    /// Looks up a block by the hash of its header.
+    pub fn block_by_hash(&self, hash: Hash) -> Option<Block> {
+        self.block_store.read_block_by_hash(hash)
+    }
+
+    /// Blocks in the `from_height..=to_height` range (inclusive), capped to what is
+    /// actually stored and to at most [`MAX_BLOCKS_IN_RANGE`] heights, so a peer
+    /// cannot force an unbounded scan by requesting e.g. `to_height: u64::MAX`.
+    pub fn blocks_in_range(&self, from_height: u64, to_height: u64) -> Vec<Block> {
+        let to_height = std::cmp::min(
+            to_height,
+            from_height.saturating_add(MAX_BLOCKS_IN_RANGE - 1),
+        );
+        (from_height..=to_height)
+            .filter_map(|height| self.block_store.read_block_by_height(height))
+            .collect()
+    }
+}
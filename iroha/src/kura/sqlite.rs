@@ -0,0 +1,187 @@
+//! A [`BlockStore`] backed by an embedded SQLite database.
+use super::BlockStore;
+use crate::prelude::*;
+use parity_scale_codec::{Decode, Encode};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+/// Stores blocks in a `blockchain(height, hash, previous_hash, block)` table of
+/// an embedded SQLite database. Appends happen inside a transaction and reject
+/// a block whose own `previous_block_hash` doesn't match the current last
+/// block's hash, giving integrity checking at append time in addition to
+/// queryable, durable storage.
+pub struct SqliteBlockStore {
+    database_path: PathBuf,
+    connection: Option<Connection>,
+}
+
+impl SqliteBlockStore {
+    pub fn new(database_path: &Path) -> Self {
+        SqliteBlockStore {
+            database_path: database_path.to_path_buf(),
+            connection: None,
+        }
+    }
+
+    fn connection(&self) -> Result<&Connection, String> {
+        self.connection
+            .as_ref()
+            .ok_or_else(|| "SqliteBlockStore used before `open`.".to_string())
+    }
+
+    fn connection_mut(&mut self) -> Result<&mut Connection, String> {
+        self.connection
+            .as_mut()
+            .ok_or_else(|| "SqliteBlockStore used before `open`.".to_string())
+    }
+}
+
+impl BlockStore for SqliteBlockStore {
+    fn open(&mut self) -> Result<(), String> {
+        let connection = Connection::open(&self.database_path)
+            .map_err(|e| format!("Failed to open block store database: {}", e))?;
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS blockchain (
+                    height INTEGER PRIMARY KEY,
+                    hash BLOB NOT NULL,
+                    previous_hash BLOB NOT NULL,
+                    block BLOB NOT NULL
+                )",
+                [],
+            )
+            .map_err(|e| format!("Failed to create blockchain table: {}", e))?;
+        self.connection = Some(connection);
+        Ok(())
+    }
+
+    fn append_block(&mut self, block: Block) -> Result<(), String> {
+        let height = self.len() as u64 + 1;
+        let expected_previous_hash = self
+            .last()
+            .map(|last_block| last_block.hash())
+            .unwrap_or_default();
+        if block.previous_block_hash() != expected_previous_hash {
+            return Err(format!(
+                "Refusing to append block at height {}: its previous_block_hash does not match the hash of the current last block.",
+                height
+            ));
+        }
+        let connection = self.connection_mut()?;
+        let transaction = connection
+            .transaction()
+            .map_err(|e| format!("Failed to start append transaction: {}", e))?;
+        transaction
+            .execute(
+                "INSERT INTO blockchain (height, hash, previous_hash, block) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    height,
+                    block.hash().encode(),
+                    expected_previous_hash.encode(),
+                    block.encode()
+                ],
+            )
+            .map_err(|e| format!("Failed to append block at height {}: {}", height, e))?;
+        transaction
+            .commit()
+            .map_err(|e| format!("Failed to commit append transaction: {}", e))?;
+        Ok(())
+    }
+
+    fn read_block_by_height(&self, height: u64) -> Option<Block> {
+        let connection = self.connection().ok()?;
+        let encoded: Vec<u8> = connection
+            .query_row(
+                "SELECT block FROM blockchain WHERE height = ?1",
+                params![height],
+                |row| row.get(0),
+            )
+            .ok()?;
+        Block::decode(&mut encoded.as_slice()).ok()
+    }
+
+    fn read_block_by_hash(&self, hash: Hash) -> Option<Block> {
+        let connection = self.connection().ok()?;
+        let encoded: Vec<u8> = connection
+            .query_row(
+                "SELECT block FROM blockchain WHERE hash = ?1",
+                params![hash.encode()],
+                |row| row.get(0),
+            )
+            .ok()?;
+        Block::decode(&mut encoded.as_slice()).ok()
+    }
+
+    fn len(&self) -> usize {
+        self.connection()
+            .ok()
+            .and_then(|connection| {
+                connection
+                    .query_row("SELECT COUNT(*) FROM blockchain", [], |row| {
+                        row.get::<_, i64>(0)
+                    })
+                    .ok()
+            })
+            .unwrap_or(0) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(previous_block_hash: Hash) -> Block {
+        Block::new(Vec::new(), previous_block_hash)
+    }
+
+    fn store() -> SqliteBlockStore {
+        let path = std::env::temp_dir().join(format!("iroha-test-kura-{}.db", unique_suffix()));
+        let mut store = SqliteBlockStore::new(&path);
+        store.open().expect("Failed to open block store.");
+        store
+    }
+
+    fn unique_suffix() -> u128 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("Time went backwards.")
+            .as_nanos()
+    }
+
+    #[test]
+    fn sequential_appends_are_stored_in_order() {
+        let mut store = store();
+        let first = block(Hash::default());
+        let second = block(first.hash());
+
+        store.append_block(first.clone()).expect("Failed to append first block.");
+        store.append_block(second.clone()).expect("Failed to append second block.");
+
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.last(), Some(second));
+    }
+
+    #[test]
+    fn append_rejects_mismatched_previous_block_hash() {
+        let mut store = store();
+        store
+            .append_block(block(Hash::default()))
+            .expect("Failed to append first block.");
+
+        let result = store.append_block(block(Hash::default()));
+
+        assert!(result.is_err());
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn blocks_round_trip_by_height_and_hash() {
+        let mut store = store();
+        let first = block(Hash::default());
+        store.append_block(first.clone()).expect("Failed to append block.");
+
+        assert_eq!(store.read_block_by_height(1), Some(first.clone()));
+        assert_eq!(store.read_block_by_hash(first.hash()), Some(first));
+        assert_eq!(store.read_block_by_height(2), None);
+    }
+}
@@ -7,6 +7,7 @@ pub mod domain;
 pub mod isi;
 mod kura;
 mod merkle;
+pub mod metrics;
 pub mod peer;
 pub mod query;
 mod queue;
@@ -18,11 +19,12 @@ pub mod wsv;
 use crate::{
     config::Configuration,
     kura::Kura,
+    metrics::Metrics,
     peer::{Peer, PeerId},
     prelude::*,
     queue::Queue,
     sumeragi::{Message, Role, Sumeragi},
-    torii::{uri, Torii},
+    torii::{uri, HttpGateway, Torii},
 };
 use futures::{
     channel::mpsc::{self, UnboundedReceiver, UnboundedSender},
@@ -32,7 +34,7 @@ use futures::{
 };
 use iroha_network::{Network, Request};
 use parity_scale_codec::{Decode, Encode};
-use std::{path::Path, sync::Arc, time::Instant};
+use std::{path::Path, sync::atomic::Ordering, sync::Arc, time::Instant};
 
 pub type BlockSender = UnboundedSender<Block>;
 pub type BlockReceiver = UnboundedReceiver<Block>;
@@ -45,6 +47,7 @@ pub type MessageReceiver = UnboundedReceiver<Message>;
 /// system. It configure, coordinate and manage transactions and queries processing, work of consensus and storage.
 pub struct Iroha {
     torii: Arc<Mutex<Torii>>,
+    http_gateway: Arc<HttpGateway>,
     queue: Arc<Mutex<Queue>>,
     sumeragi: Arc<Mutex<Sumeragi>>,
     kura: Arc<Mutex<Kura>>,
@@ -53,6 +56,7 @@ pub struct Iroha {
     blocks_receiver: Arc<Mutex<BlockReceiver>>,
     message_receiver: Arc<Mutex<MessageReceiver>>,
     world_state_view: Arc<Mutex<WorldStateView>>,
+    metrics: Arc<Metrics>,
     pool: ThreadPool,
 }
 
@@ -66,19 +70,22 @@ impl Iroha {
             &Vec::new(),
         ))));
         let pool = ThreadPool::new().expect("Failed to create new Thread Pool.");
+        let (public_key, private_key) = config.key_pair();
+        let kura = Arc::new(Mutex::new(Kura::new(
+            config.kura_block_store_kind,
+            Path::new(&config.kura_block_store_path),
+            blocks_sender,
+        )));
+        let metrics = Arc::new(Metrics::default());
         let torii = Torii::new(
             &config.torii_url,
             pool.clone(),
             Arc::clone(&world_state_view),
-            transactions_sender,
+            transactions_sender.clone(),
             message_sender,
+            Arc::clone(&kura),
+            Arc::clone(&metrics),
         );
-        let (public_key, private_key) = config.key_pair();
-        let kura = Arc::new(Mutex::new(Kura::new(
-            config.mode,
-            Path::new(&config.kura_block_store_path),
-            blocks_sender,
-        )));
         //TODO: get peers from json and blockchain
         //The id of this peer
         let iroha_peer_id = PeerId {
@@ -102,9 +109,17 @@ impl Iroha {
             .expect("Failed to initialize Sumeragi."),
         ));
         let queue = Arc::new(Mutex::new(Queue::default()));
+        let http_gateway = Arc::new(HttpGateway::new(
+            &config.torii_api_url,
+            Arc::clone(&world_state_view),
+            Arc::clone(&sumeragi),
+            Arc::clone(&queue),
+            transactions_sender,
+        ));
         Iroha {
             queue,
             torii: Arc::new(Mutex::new(torii)),
+            http_gateway,
             sumeragi,
             kura,
             world_state_view,
@@ -112,6 +127,7 @@ impl Iroha {
             blocks_receiver: Arc::new(Mutex::new(blocks_receiver)),
             message_receiver: Arc::new(Mutex::new(message_receiver)),
             last_round_time: Arc::new(Mutex::new(Instant::now())),
+            metrics,
             pool,
         }
     }
@@ -123,30 +139,49 @@ impl Iroha {
         self.pool.spawn_ok(async move {
             torii.lock().await.start().await;
         });
+        let http_gateway = Arc::clone(&self.http_gateway);
+        self.pool.spawn_ok(async move {
+            http_gateway.start().await;
+        });
         let transactions_receiver = Arc::clone(&self.transactions_receiver);
         let queue = Arc::clone(&self.queue);
+        let metrics = Arc::clone(&self.metrics);
         self.pool.spawn_ok(async move {
             while let Some(transaction) = transactions_receiver.lock().await.next().await {
-                queue.lock().await.push_pending_transaction(transaction);
+                let mut queue = queue.lock().await;
+                queue.push_pending_transaction(transaction);
+                metrics.transactions_accepted.fetch_add(1, Ordering::Relaxed);
+                metrics.set_queue_depth(queue.len() as u64);
             }
         });
         let queue = Arc::clone(&self.queue);
         let sumeragi = Arc::clone(&self.sumeragi);
         let last_round_time = Arc::clone(&self.last_round_time);
         let world_state_view = Arc::clone(&self.world_state_view);
+        let metrics = Arc::clone(&self.metrics);
         //TODO: decide what should be the minimum time to accumulate tx before creating a block
         self.pool.spawn_ok(async move {
             loop {
                 //Don't pop transactions if there is already a block in discussion
                 if !sumeragi.lock().await.has_pending_block() {
-                    let transactions = queue.lock().await.pop_pending_transactions();
+                    let transactions = {
+                        let mut queue = queue.lock().await;
+                        let transactions = queue
+                            .pop_pending_transactions(&metrics.transaction_queue_latency_ms);
+                        metrics.set_queue_depth(queue.len() as u64);
+                        transactions
+                    };
                     if !transactions.is_empty() {
                         let mut sumeragi = sumeragi.lock().await;
                         if let Role::Leader = sumeragi.role() {
-                            sumeragi
+                            let rejected_transactions = sumeragi
                                 .validate_and_store(transactions, world_state_view.clone())
                                 .await
                                 .expect("Failed to accept transactions into blockchain.");
+                            let mut queue = queue.lock().await;
+                            for transaction in &rejected_transactions {
+                                queue.penalize(&transaction.payload.account_id);
+                            }
                         } else {
                             let mut send_futures = Vec::new();
                             //TODO: send pending transactions to all peers and as leader check what tx have already been committed
@@ -173,16 +208,26 @@ impl Iroha {
         });
         let blocks_receiver = Arc::clone(&self.blocks_receiver);
         let world_state_view = Arc::clone(&self.world_state_view);
+        let last_round_time = Arc::clone(&self.last_round_time);
+        let metrics = Arc::clone(&self.metrics);
         self.pool.spawn_ok(async move {
             while let Some(block) = blocks_receiver.lock().await.next().await {
                 world_state_view.lock().await.put(&block).await;
+                metrics.blocks_committed.fetch_add(1, Ordering::Relaxed);
+                metrics.block_commit_latency_ms.record(
+                    last_round_time.lock().await.elapsed().as_millis() as u64,
+                );
             }
         });
         let message_receiver = Arc::clone(&self.message_receiver);
         let sumeragi = Arc::clone(&self.sumeragi);
+        let metrics = Arc::clone(&self.metrics);
         self.pool.spawn_ok(async move {
             while let Some(message) = message_receiver.lock().await.next().await {
                 let _result = sumeragi.lock().await.handle_message(message).await;
+                metrics
+                    .consensus_messages_handled
+                    .fetch_add(1, Ordering::Relaxed);
             }
         });
         Ok(())
@@ -0,0 +1,181 @@
+//! Counters and latency histograms for observing `Iroha`'s throughput and tail
+//! latencies, scraped through a `Torii` endpoint.
+use parity_scale_codec::{Decode, Encode};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Exponential bucket boundaries (in milliseconds): 1, 2, 4, ..., 2^19.
+const HISTOGRAM_BOUNDARIES_MS: [u64; 20] = [
+    1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1_024, 2_048, 4_096, 8_192, 16_384, 32_768, 65_536,
+    131_072, 262_144, 524_288,
+];
+
+/// A fixed-boundary exponential histogram recording per-bucket counts plus the
+/// overall min/max/sum, from which percentiles can be derived.
+pub struct Histogram {
+    boundaries_ms: &'static [u64],
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_ms: AtomicU64,
+    min_ms: AtomicU64,
+    max_ms: AtomicU64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Histogram {
+            boundaries_ms: &HISTOGRAM_BOUNDARIES_MS,
+            buckets: (0..=HISTOGRAM_BOUNDARIES_MS.len())
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            count: AtomicU64::new(0),
+            sum_ms: AtomicU64::new(0),
+            min_ms: AtomicU64::new(u64::MAX),
+            max_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a single observation, in milliseconds.
+    pub fn record(&self, value_ms: u64) {
+        let bucket = self
+            .boundaries_ms
+            .iter()
+            .position(|&boundary| value_ms <= boundary)
+            .unwrap_or(self.boundaries_ms.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(value_ms, Ordering::Relaxed);
+        self.min_ms.fetch_min(value_ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(value_ms, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        let count = self.count.load(Ordering::Relaxed);
+        HistogramSnapshot {
+            boundaries_ms: self.boundaries_ms.to_vec(),
+            bucket_counts: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect(),
+            count,
+            sum_ms: self.sum_ms.load(Ordering::Relaxed),
+            min_ms: if count == 0 {
+                0
+            } else {
+                self.min_ms.load(Ordering::Relaxed)
+            },
+            max_ms: self.max_ms.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram::new()
+    }
+}
+
+/// A point-in-time, SCALE-encodable copy of a [`Histogram`], suitable for
+/// scraping.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct HistogramSnapshot {
+    pub boundaries_ms: Vec<u64>,
+    pub bucket_counts: Vec<u64>,
+    pub count: u64,
+    pub sum_ms: u64,
+    pub min_ms: u64,
+    pub max_ms: u64,
+}
+
+/// Counters and latency histograms recorded from `Iroha::start`'s processing
+/// loops and from `torii::handle_request`.
+pub struct Metrics {
+    pub transactions_accepted: AtomicU64,
+    pub blocks_committed: AtomicU64,
+    pub consensus_messages_handled: AtomicU64,
+    pub queue_depth: AtomicU64,
+    /// Time a transaction spends in `Queue` before being included in a block.
+    pub transaction_queue_latency_ms: Histogram,
+    /// Time between a block build being kicked off and it being committed to the `WorldStateView`.
+    pub block_commit_latency_ms: Histogram,
+    /// Time spent inside `torii::handle_request`.
+    pub request_handling_latency_ms: Histogram,
+}
+
+impl Metrics {
+    /// Records the current size of `Queue`.
+    pub fn set_queue_depth(&self, depth: u64) {
+        self.queue_depth.store(depth, Ordering::Relaxed);
+    }
+
+    /// Takes a consistent, SCALE-encodable copy of all counters and histograms.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            transactions_accepted: self.transactions_accepted.load(Ordering::Relaxed),
+            blocks_committed: self.blocks_committed.load(Ordering::Relaxed),
+            consensus_messages_handled: self.consensus_messages_handled.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+            transaction_queue_latency_ms: self.transaction_queue_latency_ms.snapshot(),
+            block_commit_latency_ms: self.block_commit_latency_ms.snapshot(),
+            request_handling_latency_ms: self.request_handling_latency_ms.snapshot(),
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics {
+            transactions_accepted: AtomicU64::new(0),
+            blocks_committed: AtomicU64::new(0),
+            consensus_messages_handled: AtomicU64::new(0),
+            queue_depth: AtomicU64::new(0),
+            transaction_queue_latency_ms: Histogram::new(),
+            block_commit_latency_ms: Histogram::new(),
+            request_handling_latency_ms: Histogram::new(),
+        }
+    }
+}
+
+/// A point-in-time, SCALE-encodable copy of [`Metrics`], returned by the
+/// `torii` metrics endpoint.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct MetricsSnapshot {
+    pub transactions_accepted: u64,
+    pub blocks_committed: u64,
+    pub consensus_messages_handled: u64,
+    pub queue_depth: u64,
+    pub transaction_queue_latency_ms: HistogramSnapshot,
+    pub block_commit_latency_ms: HistogramSnapshot,
+    pub request_handling_latency_ms: HistogramSnapshot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_by_exponential_boundary() {
+        let histogram = Histogram::new();
+        histogram.record(1);
+        histogram.record(3);
+        histogram.record(1_000_000);
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count, 3);
+        assert_eq!(snapshot.min_ms, 1);
+        assert_eq!(snapshot.max_ms, 1_000_000);
+        assert_eq!(snapshot.bucket_counts[0], 1);
+        assert_eq!(*snapshot.bucket_counts.last().unwrap(), 1);
+    }
+
+    #[test]
+    fn counters_increment_independently() {
+        let metrics = Metrics::default();
+        metrics.transactions_accepted.fetch_add(1, Ordering::Relaxed);
+        metrics.set_queue_depth(5);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.transactions_accepted, 1);
+        assert_eq!(snapshot.queue_depth, 5);
+    }
+}
@@ -0,0 +1,293 @@
+//! This module contains `Queue` that is used to accumulate transactions before
+//! they get into the block.
+use crate::{metrics::Histogram, prelude::*};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// Upper bound on the total number of transactions the queue will hold at once.
+const DEFAULT_MAX_TRANSACTIONS_IN_QUEUE: usize = 2_usize.pow(16);
+/// Maximum number of transactions popped into a single block.
+const DEFAULT_TRANSACTIONS_IN_BLOCK: usize = 2_usize.pow(9);
+/// Share (in percent) of `max_transactions_in_queue` a single account is allowed to occupy.
+const DEFAULT_MAX_TRANSACTIONS_PER_SENDER_PERCENT: u64 = 1;
+/// How long a "future" (not yet ready) transaction is kept before being pruned.
+const DEFAULT_FUTURE_TRANSACTION_TTL_MS: u64 = 60_000;
+/// How much a single failed validation weighs against an account's score.
+const VALIDATION_FAILURE_WEIGHT: i64 = 1_000_000;
+
+/// A transaction held by the queue together with the bookkeeping needed to order
+/// and evict it. Its score is not cached here: a sender's penalty can change
+/// after the transaction was enqueued, so the score is always recomputed live
+/// from `Queue::penalties` via `Queue::score_of`.
+struct WaitingTransaction {
+    transaction: Transaction,
+    received_at: Instant,
+}
+
+/// A bounded, per-account priority pool of pending transactions.
+///
+/// Transactions are grouped by sender into sub-queues ordered by arrival sequence.
+/// Only the head of each sub-queue is considered `ready` (i.e. a candidate to be
+/// popped into the next block); the rest are `future` and wait for their turn.
+/// The pool as a whole is capped by `max_transactions_in_queue`, with a further
+/// per-sender cap of `max_transactions_per_sender`; once a cap is hit, the
+/// lowest-scoring transaction (within the relevant scope) is evicted to make room.
+pub struct Queue {
+    accounts: HashMap<Id, BTreeMap<u64, WaitingTransaction>>,
+    len: usize,
+    next_sequence: u64,
+    /// Accumulated validation-failure penalties per sender, used to push repeat
+    /// offenders towards the back of the pool.
+    penalties: HashMap<Id, i64>,
+    max_transactions_in_queue: usize,
+    max_transactions_per_sender: usize,
+    transactions_in_block: usize,
+    future_transaction_ttl: Duration,
+}
+
+impl Queue {
+    /// Constructs an empty queue with explicit limits.
+    pub fn new(
+        max_transactions_in_queue: usize,
+        transactions_in_block: usize,
+        future_transaction_ttl: Duration,
+    ) -> Self {
+        let max_transactions_per_sender = std::cmp::max(
+            1,
+            max_transactions_in_queue as u64 * DEFAULT_MAX_TRANSACTIONS_PER_SENDER_PERCENT / 100,
+        ) as usize;
+        Queue {
+            accounts: HashMap::new(),
+            len: 0,
+            next_sequence: 0,
+            penalties: HashMap::new(),
+            max_transactions_in_queue,
+            max_transactions_per_sender,
+            transactions_in_block,
+            future_transaction_ttl,
+        }
+    }
+
+    /// Total number of transactions currently held across all senders.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    fn score_of(&self, account_id: &Id, sequence: u64) -> i64 {
+        let penalty = self.penalties.get(account_id).copied().unwrap_or(0);
+        sequence as i64 + penalty * VALIDATION_FAILURE_WEIGHT
+    }
+
+    /// Finds the worst-scoring (highest score) transaction in the whole pool, if any.
+    fn worst(&self) -> Option<(Id, u64)> {
+        self.accounts
+            .iter()
+            .flat_map(|(account_id, queue)| {
+                queue
+                    .keys()
+                    .map(move |sequence| (self.score_of(account_id, *sequence), account_id.clone(), *sequence))
+            })
+            .max_by_key(|(score, _, _)| *score)
+            .map(|(_, account_id, sequence)| (account_id, sequence))
+    }
+
+    /// Finds the worst-scoring (highest score) transaction within a single
+    /// sender's sub-queue, if any.
+    fn worst_for_sender(&self, account_id: &Id) -> Option<u64> {
+        self.accounts.get(account_id).and_then(|sender_queue| {
+            sender_queue
+                .keys()
+                .max_by_key(|&&sequence| self.score_of(account_id, sequence))
+                .copied()
+        })
+    }
+
+    fn remove(&mut self, account_id: &Id, sequence: u64) {
+        if let Some(queue) = self.accounts.get_mut(account_id) {
+            if queue.remove(&sequence).is_some() {
+                self.len -= 1;
+            }
+            if queue.is_empty() {
+                self.accounts.remove(account_id);
+            }
+        }
+    }
+
+    /// Accepts a transaction into the pool, evicting lower-priority transactions
+    /// if the pool or the sender's share of it is full.
+    pub fn push_pending_transaction(&mut self, transaction: Transaction) {
+        let account_id = transaction.payload.account_id.clone();
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let score = self.score_of(&account_id, sequence);
+
+        if self.len >= self.max_transactions_in_queue {
+            match self.worst() {
+                Some((worst_account, worst_sequence))
+                    if self.score_of(&worst_account, worst_sequence) > score =>
+                {
+                    self.remove(&worst_account, worst_sequence);
+                }
+                _ => return,
+            }
+        }
+
+        let sender_cap_hit = self
+            .accounts
+            .get(&account_id)
+            .map_or(false, |sender_queue| {
+                sender_queue.len() >= self.max_transactions_per_sender
+            });
+        if sender_cap_hit {
+            match self.worst_for_sender(&account_id) {
+                Some(worst_sequence) if self.score_of(&account_id, worst_sequence) > score => {
+                    self.remove(&account_id, worst_sequence);
+                }
+                _ => return,
+            }
+        }
+
+        self.accounts.entry(account_id).or_default().insert(
+            sequence,
+            WaitingTransaction {
+                transaction,
+                received_at: Instant::now(),
+            },
+        );
+        self.len += 1;
+    }
+
+    /// Demotes the score of `account_id`'s pending transactions, e.g. after one of
+    /// them failed validation, so that repeat offenders are the first to be evicted.
+    pub fn penalize(&mut self, account_id: &Id) {
+        *self.penalties.entry(account_id.clone()).or_insert(0) += 1;
+    }
+
+    /// Drops transactions that have been sitting in the queue, unready, for longer
+    /// than `future_transaction_ttl`.
+    fn prune_expired_futures(&mut self) {
+        let ttl = self.future_transaction_ttl;
+        for queue in self.accounts.values_mut() {
+            let Some(&ready_sequence) = queue.keys().next() else {
+                continue;
+            };
+            let expired: Vec<u64> = queue
+                .iter()
+                .filter(|(&sequence, waiting)| {
+                    sequence != ready_sequence && waiting.received_at.elapsed() > ttl
+                })
+                .map(|(&sequence, _)| sequence)
+                .collect();
+            for sequence in expired {
+                queue.remove(&sequence);
+                self.len -= 1;
+            }
+        }
+        self.accounts.retain(|_, queue| !queue.is_empty());
+    }
+
+    /// Returns the ready transactions (at most one per sender - the next expected
+    /// one) up to `transactions_in_block`, removing them from the pool and
+    /// recording how long each one waited into `queue_latency`.
+    pub fn pop_pending_transactions(&mut self, queue_latency: &Histogram) -> Vec<Transaction> {
+        self.prune_expired_futures();
+
+        let mut ready: Vec<(i64, Id, u64)> = self
+            .accounts
+            .iter()
+            .filter_map(|(account_id, queue)| {
+                queue.keys().next().map(|&sequence| {
+                    (self.score_of(account_id, sequence), account_id.clone(), sequence)
+                })
+            })
+            .collect();
+        ready.sort_by_key(|(score, _, _)| *score);
+        ready.truncate(self.transactions_in_block);
+
+        ready
+            .into_iter()
+            .map(|(_, account_id, sequence)| {
+                let waiting = self
+                    .accounts
+                    .get_mut(&account_id)
+                    .and_then(|queue| queue.remove(&sequence))
+                    .expect("Ready transaction disappeared from its sub-queue.");
+                queue_latency.record(waiting.received_at.elapsed().as_millis() as u64);
+                self.len -= 1;
+                if self
+                    .accounts
+                    .get(&account_id)
+                    .map_or(false, |queue| queue.is_empty())
+                {
+                    self.accounts.remove(&account_id);
+                }
+                waiting.transaction
+            })
+            .collect()
+    }
+}
+
+impl Default for Queue {
+    fn default() -> Self {
+        Queue::new(
+            DEFAULT_MAX_TRANSACTIONS_IN_QUEUE,
+            DEFAULT_TRANSACTIONS_IN_BLOCK,
+            Duration::from_millis(DEFAULT_FUTURE_TRANSACTION_TTL_MS),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(account_name: &str) -> Transaction {
+        Transaction::new(Vec::new(), Id::new(account_name, "test"), 1000)
+    }
+
+    #[test]
+    fn ready_transaction_is_popped_before_future_ones() {
+        let mut queue = Queue::default();
+        queue.push_pending_transaction(transaction("alice"));
+        queue.push_pending_transaction(transaction("alice"));
+        queue.push_pending_transaction(transaction("bob"));
+
+        let popped = queue.pop_pending_transactions(&Histogram::default());
+
+        assert_eq!(popped.len(), 2);
+        assert_eq!(queue.len, 1);
+    }
+
+    #[test]
+    fn per_sender_cap_evicts_lowest_scoring_transaction() {
+        let mut queue = Queue::new(100, 100, Duration::from_secs(60));
+        for _ in 0..2 {
+            queue.push_pending_transaction(transaction("alice"));
+        }
+
+        assert_eq!(queue.accounts.get(&Id::new("alice", "test")).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn full_pool_evicts_worst_scoring_transaction() {
+        let mut queue = Queue::new(1, 1, Duration::from_secs(60));
+        queue.push_pending_transaction(transaction("alice"));
+        queue.push_pending_transaction(transaction("bob"));
+
+        assert_eq!(queue.len, 1);
+        assert!(queue.accounts.contains_key(&Id::new("alice", "test")));
+    }
+
+    #[test]
+    fn penalized_sender_is_evicted_first() {
+        let mut queue = Queue::new(2, 2, Duration::from_secs(60));
+        queue.push_pending_transaction(transaction("alice"));
+        queue.penalize(&Id::new("alice", "test"));
+        queue.push_pending_transaction(transaction("bob"));
+        queue.push_pending_transaction(transaction("carol"));
+
+        assert!(!queue.accounts.contains_key(&Id::new("alice", "test")));
+    }
+}
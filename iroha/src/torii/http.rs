@@ -0,0 +1,230 @@
+//! A JSON-over-HTTP gateway in front of [`Torii`](super::Torii), for clients
+//! that don't speak the SCALE-encoded `iroha_network` protocol.
+use crate::{prelude::*, sumeragi::{Role, Sumeragi}, queue::Queue};
+use futures::lock::Mutex;
+use serde::Serialize;
+use std::{convert::Infallible, sync::Arc};
+use warp::Filter;
+
+pub mod uri {
+    pub const TRANSACTION_URI: &str = "api/v1/transaction";
+    pub const QUERY_URI: &str = "api/v1/query";
+    pub const STATUS_URI: &str = "api/v1/status";
+}
+
+/// Snapshot of the node's state, returned by [`uri::STATUS_URI`].
+#[derive(Debug, Serialize)]
+pub struct NodeStatus {
+    pub block_height: u64,
+    pub role: String,
+    pub peers: usize,
+    pub pending_transactions: usize,
+}
+
+/// Exposes a small REST/JSON API in front of `Torii`: submitting transactions,
+/// running queries and reading node status, all routed through the same
+/// `transaction_sender`/query-execute paths `Torii` itself uses.
+pub struct HttpGateway {
+    url: String,
+    world_state_view: Arc<Mutex<WorldStateView>>,
+    sumeragi: Arc<Mutex<Sumeragi>>,
+    queue: Arc<Mutex<Queue>>,
+    transaction_sender: Arc<Mutex<TransactionSender>>,
+}
+
+impl HttpGateway {
+    pub fn new(
+        url: &str,
+        world_state_view: Arc<Mutex<WorldStateView>>,
+        sumeragi: Arc<Mutex<Sumeragi>>,
+        queue: Arc<Mutex<Queue>>,
+        transaction_sender: TransactionSender,
+    ) -> Self {
+        HttpGateway {
+            url: url.to_string(),
+            world_state_view,
+            sumeragi,
+            queue,
+            transaction_sender: Arc::new(Mutex::new(transaction_sender)),
+        }
+    }
+
+    /// Builds the gateway's route filter, kept separate from [`start`](HttpGateway::start)
+    /// so tests can drive it directly with `warp::test` instead of binding a socket.
+    fn routes(&self) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        let with_world_state_view = warp::any().map({
+            let world_state_view = Arc::clone(&self.world_state_view);
+            move || Arc::clone(&world_state_view)
+        });
+        let with_sumeragi = warp::any().map({
+            let sumeragi = Arc::clone(&self.sumeragi);
+            move || Arc::clone(&sumeragi)
+        });
+        let with_queue = warp::any().map({
+            let queue = Arc::clone(&self.queue);
+            move || Arc::clone(&queue)
+        });
+        let with_transaction_sender = warp::any().map({
+            let transaction_sender = Arc::clone(&self.transaction_sender);
+            move || Arc::clone(&transaction_sender)
+        });
+
+        let transaction = warp::path!("api" / "v1" / "transaction")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_transaction_sender)
+            .and_then(submit_transaction);
+        let query = warp::path!("api" / "v1" / "query")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_world_state_view)
+            .and_then(execute_query);
+        let status = warp::path!("api" / "v1" / "status")
+            .and(warp::get())
+            .and(with_world_state_view)
+            .and(with_sumeragi)
+            .and(with_queue)
+            .and_then(node_status);
+
+        transaction.or(query).or(status)
+    }
+
+    pub async fn start(&self) {
+        let address: std::net::SocketAddr =
+            self.url.parse().expect("Failed to parse HTTP gateway address.");
+        warp::serve(self.routes()).run(address).await;
+    }
+}
+
+async fn submit_transaction(
+    request: TransactionRequest,
+    transaction_sender: Arc<Mutex<TransactionSender>>,
+) -> Result<impl warp::Reply, Infallible> {
+    use futures::sink::SinkExt;
+
+    match Transaction::from(request).accept() {
+        Ok(transaction) => match transaction_sender.lock().await.send(transaction).await {
+            Ok(()) => Ok(warp::reply::with_status(
+                warp::reply::json(&"Transaction accepted."),
+                warp::http::StatusCode::OK,
+            )),
+            Err(e) => Ok(warp::reply::with_status(
+                warp::reply::json(&format!("Failed to enqueue transaction: {}", e)),
+                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+            )),
+        },
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&format!("Failed to accept transaction: {}", e)),
+            warp::http::StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+async fn execute_query(
+    request: QueryRequest,
+    world_state_view: Arc<Mutex<WorldStateView>>,
+) -> Result<impl warp::Reply, Infallible> {
+    match request.query.execute(&*world_state_view.lock().await) {
+        Ok(result) => Ok(warp::reply::with_status(
+            warp::reply::json(&result),
+            warp::http::StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&e),
+            warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+        )),
+    }
+}
+
+async fn node_status(
+    world_state_view: Arc<Mutex<WorldStateView>>,
+    sumeragi: Arc<Mutex<Sumeragi>>,
+    queue: Arc<Mutex<Queue>>,
+) -> Result<impl warp::Reply, Infallible> {
+    let sumeragi = sumeragi.lock().await;
+    let status = NodeStatus {
+        block_height: world_state_view.lock().await.height(),
+        role: match sumeragi.role() {
+            Role::Leader => "Leader".to_string(),
+            role => format!("{:?}", role),
+        },
+        peers: sumeragi.peers().len(),
+        pending_transactions: queue.lock().await.len(),
+    };
+    Ok(warp::reply::json(&status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Configuration, kura::Kura, peer::PeerId, sumeragi::Sumeragi};
+    use futures::channel::mpsc;
+
+    const CONFIGURATION_PATH: &str = "tests/test_config.json";
+
+    async fn gateway() -> HttpGateway {
+        let config =
+            Configuration::from_path(CONFIGURATION_PATH).expect("Failed to load configuration.");
+        let (public_key, private_key) = config.key_pair();
+        let world_state_view = Arc::new(Mutex::new(WorldStateView::new(Peer::new(
+            config.torii_url.clone(),
+            &Vec::new(),
+        ))));
+        let peer_id = PeerId {
+            address: config.torii_url.clone(),
+            public_key: public_key.clone(),
+        };
+        let (blocks_sender, _blocks_receiver) = mpsc::unbounded();
+        let kura = Arc::new(Mutex::new(Kura::new(
+            crate::config::BlockStoreKind::File,
+            std::path::Path::new("./blocks"),
+            blocks_sender,
+        )));
+        let sumeragi = Arc::new(Mutex::new(
+            Sumeragi::new(
+                public_key,
+                private_key,
+                &[peer_id.clone()],
+                peer_id,
+                None,
+                config.max_faulty_peers,
+                kura,
+            )
+            .expect("Failed to initialize Sumeragi."),
+        ));
+        let queue = Arc::new(Mutex::new(Queue::default()));
+        let (transaction_sender, _transaction_receiver) = mpsc::unbounded();
+        HttpGateway::new(
+            &config.torii_api_url,
+            world_state_view,
+            sumeragi,
+            queue,
+            transaction_sender,
+        )
+    }
+
+    // Regression test for the `warp::path` vs `warp::path!` bug: `warp::path(p)` only
+    // matches a single path segment, so a multi-segment literal like "api/v1/status"
+    // never matches a real request and every route 404s.
+    #[async_std::test]
+    async fn status_route_matches_its_multi_segment_path() {
+        let gateway = gateway().await;
+        let response = warp::test::request()
+            .method("GET")
+            .path("/api/v1/status")
+            .reply(&gateway.routes())
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::OK);
+    }
+
+    #[async_std::test]
+    async fn unrouted_path_is_not_found() {
+        let gateway = gateway().await;
+        let response = warp::test::request()
+            .method("GET")
+            .path("/status")
+            .reply(&gateway.routes())
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::NOT_FOUND);
+    }
+}
@@ -1,8 +1,13 @@
-use crate::{prelude::*, sumeragi::Message, MessageSender};
+mod http;
+
+pub use http::HttpGateway;
+
+use crate::{kura::Kura, metrics::Metrics, prelude::*, sumeragi::Message, MessageSender};
 use futures::{executor::ThreadPool, lock::Mutex};
 use iroha_derive::log;
 use iroha_network::prelude::*;
-use std::{convert::TryFrom, sync::Arc};
+use parity_scale_codec::{Decode, Encode};
+use std::{convert::TryFrom, sync::Arc, time::Instant};
 
 pub struct Torii {
     url: String,
@@ -10,6 +15,8 @@ pub struct Torii {
     world_state_view: Arc<Mutex<WorldStateView>>,
     transaction_sender: Arc<Mutex<TransactionSender>>,
     message_sender: Arc<Mutex<MessageSender>>,
+    kura: Arc<Mutex<Kura>>,
+    metrics: Arc<Metrics>,
 }
 
 impl Torii {
@@ -19,6 +26,8 @@ impl Torii {
         world_state_view: Arc<Mutex<WorldStateView>>,
         transaction_sender: TransactionSender,
         message_sender: MessageSender,
+        kura: Arc<Mutex<Kura>>,
+        metrics: Arc<Metrics>,
     ) -> Self {
         Torii {
             url: url.to_string(),
@@ -26,6 +35,8 @@ impl Torii {
             pool_ref,
             transaction_sender: Arc::new(Mutex::new(transaction_sender)),
             message_sender: Arc::new(Mutex::new(message_sender)),
+            kura,
+            metrics,
         }
     }
 
@@ -34,11 +45,15 @@ impl Torii {
         let world_state_view = Arc::clone(&self.world_state_view);
         let transaction_sender = Arc::clone(&self.transaction_sender);
         let message_sender = Arc::clone(&self.message_sender);
+        let kura = Arc::clone(&self.kura);
+        let metrics = Arc::clone(&self.metrics);
         let state = ToriiState {
             pool: self.pool_ref.clone(),
             world_state_view,
             transaction_sender,
             message_sender,
+            kura,
+            metrics,
         };
         Network::listen(Arc::new(Mutex::new(state)), url, handle_connection)
             .await
@@ -51,6 +66,8 @@ struct ToriiState {
     world_state_view: Arc<Mutex<WorldStateView>>,
     transaction_sender: Arc<Mutex<TransactionSender>>,
     message_sender: Arc<Mutex<MessageSender>>,
+    kura: Arc<Mutex<Kura>>,
+    metrics: Arc<Metrics>,
 }
 
 async fn handle_connection(
@@ -69,6 +86,21 @@ async fn handle_connection(
 
 #[log]
 async fn handle_request(state: State<ToriiState>, request: Request) -> Result<Response, String> {
+    let start_time = Instant::now();
+    let result = handle_request_inner(Arc::clone(&state), request).await;
+    state
+        .lock()
+        .await
+        .metrics
+        .request_handling_latency_ms
+        .record(start_time.elapsed().as_millis() as u64);
+    result
+}
+
+async fn handle_request_inner(
+    state: State<ToriiState>,
+    request: Request,
+) -> Result<Response, String> {
     match request.url() {
         uri::INSTRUCTIONS_URI => match Transaction::try_from(request.payload().to_vec()) {
             Ok(transaction) => {
@@ -123,14 +155,86 @@ async fn handle_request(state: State<ToriiState>, request: Request) -> Result<Re
                 Ok(Response::InternalError)
             }
         },
+        uri::BLOCKS_GET => match BlocksRequest::decode(&mut request.payload()) {
+            Ok(request) => {
+                let kura = state.lock().await.kura.clone();
+                let blocks = kura
+                    .lock()
+                    .await
+                    .blocks_in_range(request.from_height, request.to_height);
+                Ok(Response::Ok(blocks.encode()))
+            }
+            Err(e) => {
+                eprintln!("Failed to decode blocks request: {}", e);
+                Ok(Response::InternalError)
+            }
+        },
+        uri::BLOCKS_EXIST => match BlockExistsRequest::decode(&mut request.payload()) {
+            Ok(request) => {
+                let kura = state.lock().await.kura.clone();
+                let kura = kura.lock().await;
+                let exists = match request {
+                    BlockExistsRequest::Hash(hash) => kura.block_by_hash(hash).is_some(),
+                    BlockExistsRequest::Height(height) => kura.block_by_height(height).is_some(),
+                };
+                Ok(Response::Ok(exists.encode()))
+            }
+            Err(e) => {
+                eprintln!("Failed to decode block-exists request: {}", e);
+                Ok(Response::InternalError)
+            }
+        },
+        uri::BLOCKS_PUT => match Block::try_from(request.payload().to_vec()) {
+            Ok(block) => {
+                let kura = state.lock().await.kura.clone();
+                match kura.lock().await.store_block(block).await {
+                    Ok(()) => Ok(Response::empty_ok()),
+                    Err(e) => {
+                        eprintln!("Failed to store block received via BLOCKS_PUT: {}", e);
+                        Ok(Response::InternalError)
+                    }
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to decode block: {}", e);
+                Ok(Response::InternalError)
+            }
+        },
+        uri::METRICS_URI => {
+            let snapshot = state.lock().await.metrics.snapshot();
+            Ok(Response::Ok(snapshot.encode()))
+        }
         non_supported_uri => panic!("URI not supported: {}.", &non_supported_uri),
     }
 }
 
+/// Request body for [`uri::BLOCKS_GET`]: fetch committed blocks by height range.
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BlocksRequest {
+    pub from_height: u64,
+    pub to_height: u64,
+}
+
+/// Request body for [`uri::BLOCKS_EXIST`]: ask whether a block is stored, identified
+/// either by its hash or by its height.
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum BlockExistsRequest {
+    Hash(Hash),
+    Height(u64),
+}
+
 pub mod uri {
     pub const QUERY_URI: &str = "/query";
     pub const INSTRUCTIONS_URI: &str = "/instruction";
     pub const BLOCKS_URI: &str = "/block";
+    /// Fetch committed blocks by height range, for peers catching up.
+    pub const BLOCKS_GET: &str = "/block/get";
+    /// Ask whether a block with a given hash is present.
+    pub const BLOCKS_EXIST: &str = "/block/exist";
+    /// Submit a committed block for import.
+    pub const BLOCKS_PUT: &str = "/block/put";
+    /// Scrape a snapshot of the node's [`crate::metrics::Metrics`].
+    pub const METRICS_URI: &str = "/metrics";
 }
 
 #[cfg(test)]
@@ -150,6 +254,7 @@ mod tests {
         let torii_url = config.torii_url.to_string();
         let (tx_tx, _) = mpsc::unbounded();
         let (ms_tx, _) = mpsc::unbounded();
+        let (bs_tx, _) = mpsc::unbounded();
         let mut torii = Torii::new(
             &torii_url,
             ThreadPool::new().expect("Failed to build Thread Pool."),
@@ -159,6 +264,12 @@ mod tests {
             )))),
             tx_tx,
             ms_tx,
+            Arc::new(Mutex::new(Kura::new(
+                crate::config::BlockStoreKind::File,
+                std::path::Path::new("./blocks"),
+                bs_tx,
+            ))),
+            Arc::new(crate::metrics::Metrics::default()),
         );
         task::spawn(async move {
             torii.start().await;